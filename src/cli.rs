@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::sync::LazyLock;
 
 fn generate_version() -> String {
@@ -26,6 +26,15 @@ pub enum Commands {
     Open {
         project_name: String,
     },
+    Pwd {
+        project_name: String,
+    },
+    Build {
+        project_name: String,
+    },
+    Run {
+        project_name: String,
+    },
     Add {
         directory: String,
     },
@@ -44,6 +53,35 @@ pub enum Commands {
         languages: bool,
         #[arg(short, long)]
         source: bool,
+        #[arg(short, long)]
+        tags: bool,
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    Init {
+        shell: ShellKind,
+    },
+    Sync,
+    Import {
+        owner: String,
     },
     Edit,
+    Inspect,
+}
+
+#[derive(Subcommand)]
+pub enum TagAction {
+    Add { project_name: String, tag: String },
+    Rm { project_name: String, tag: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
 }
\ No newline at end of file
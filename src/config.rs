@@ -1,11 +1,13 @@
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, ExitStatus};
 
 use git2::Repository;
 use prettytable::{cell, Row, Table};
+use serde::Deserialize;
 
-use crate::parse::{Project, ProjectConfig, Source};
+use crate::cli::ShellKind;
+use crate::parse::{Build, BuildType, Project, ProjectConfig, Source};
 use crate::Result;
 
 const DEFAULT_ROOT_DIR: &str = "project-manager/projects.json";
@@ -20,40 +22,181 @@ pub mod fetchers {
 
     pub struct GitFetcher;
 
-    impl GitFetcher {
-        fn clone_repository(url: &str, project_dir: &Path) -> Result<Repository> {
-            let mut callbacks = git2::RemoteCallbacks::new();
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                if let Some(username) = username_from_url {
-                    git2::Cred::ssh_key_from_agent(username)
-                } else {
-                    Err(git2::Error::from_str("git Username not provided"))
-                }
-            });
+    /// Authenticates remote operations through the user's SSH agent.
+    fn credentials_callback(
+        _url: &str,
+        username_from_url: Option<&str>,
+        _allowed_types: git2::CredentialType,
+    ) -> std::result::Result<git2::Cred, git2::Error> {
+        if let Some(username) = username_from_url {
+            git2::Cred::ssh_key_from_agent(username)
+        } else {
+            Err(git2::Error::from_str("git Username not provided"))
+        }
+    }
 
-            let mut fetch_options = git2::FetchOptions::new();
-            fetch_options.remote_callbacks(callbacks);
+    /// Builds fetch options wired to the shared SSH-agent credential callback.
+    fn fetch_options<'a>() -> git2::FetchOptions<'a> {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(credentials_callback);
+        let mut options = git2::FetchOptions::new();
+        options.remote_callbacks(callbacks);
+        options
+    }
 
+    impl GitFetcher {
+        fn clone_repository(url: &str, project_dir: &Path) -> std::result::Result<Repository, git2::Error> {
             let mut builder = git2::build::RepoBuilder::new();
-            builder.fetch_options(fetch_options);
+            builder.fetch_options(fetch_options());
+
+            builder.clone(url, project_dir)
+        }
 
-            Ok(builder.clone(url, project_dir)?)
+        /// Open an existing clone and confirm it still resolves what we need,
+        /// so a half-written directory is treated as unusable.
+        fn open_valid(
+            source: &Source,
+            project_dir: &Path,
+        ) -> std::result::Result<Repository, git2::Error> {
+            let repo = Repository::open(project_dir)?;
+            match &source.revision {
+                Some(revision) => {
+                    repo.revparse_single(revision)?;
+                }
+                None => {
+                    repo.head()?;
+                }
+            }
+            Ok(repo)
         }
+
+        /// Reuse a valid clone, or (re-)clone it, recovering exactly once from a
+        /// corrupt or interrupted checkout. Network and auth failures propagate
+        /// untouched so we don't hammer a flaky connection with re-clones.
+        fn prepare_repository(source: &Source, project_dir: &Path) -> Result<Repository> {
+            if project_dir.exists() {
+                match Self::open_valid(source, project_dir) {
+                    Ok(repo) => return Ok(repo),
+                    Err(_) => std::fs::remove_dir_all(project_dir)?,
+                }
+            }
+
+            match Self::clone_repository(&source.url, project_dir) {
+                Ok(repo) => Ok(repo),
+                Err(err) if is_corruption_error(&err) => {
+                    if project_dir.exists() {
+                        std::fs::remove_dir_all(project_dir)?;
+                    }
+                    Ok(Self::clone_repository(&source.url, project_dir)?)
+                }
+                Err(err) => Err(err.into()),
+            }
+        }
+    }
+
+    /// Whether an error out of a clone/fetch looks like on-disk corruption
+    /// (bad references, failed checkout, invalid object DB) as opposed to a
+    /// transport-level problem we should not recover from by re-cloning.
+    fn is_corruption_error(err: &git2::Error) -> bool {
+        use git2::ErrorClass;
+        matches!(
+            err.class(),
+            ErrorClass::Reference
+                | ErrorClass::Object
+                | ErrorClass::Odb
+                | ErrorClass::Checkout
+                | ErrorClass::Index
+                | ErrorClass::Repository
+        )
     }
 
     impl SourceFetcher for GitFetcher {
         fn fetch_source(&self, source: &Source, project_dir: &Path) -> Result<PathBuf> {
-            let repo = Self::clone_repository(&source.url, project_dir)?;
-            Ok(repo.path().to_path_buf())
+            let repo = Self::prepare_repository(source, project_dir)?;
+
+            // Pin the working tree to an exact revision so clones are reproducible.
+            if let Some(revision) = &source.revision {
+                let object = repo.revparse_single(revision)?;
+                repo.checkout_tree(&object, None)?;
+                repo.set_head_detached(object.id())?;
+            }
+
+            Ok(usable_dir(source, project_dir))
+        }
+    }
+
+    /// Uses a source that already lives on the local filesystem as-is.
+    pub struct LocalFetcher;
+
+    impl SourceFetcher for LocalFetcher {
+        fn fetch_source(&self, source: &Source, project_dir: &Path) -> Result<PathBuf> {
+            let crate::parse::SourceType::Local { path } = &source.source_type else {
+                return Err("LocalFetcher used with a non-local source".into());
+            };
+            if !Path::new(path).exists() {
+                return Err(format!("Local source path does not exist: {}", path).into());
+            }
+            Ok(usable_dir(source, project_dir))
+        }
+    }
+
+    /// The on-disk directory a source actually resolves to: the `Local` path or
+    /// the clone root, narrowed to `subpath` when one is set.
+    pub fn usable_dir(source: &Source, project_dir: &Path) -> PathBuf {
+        let base = match &source.source_type {
+            crate::parse::SourceType::Local { path } => PathBuf::from(path),
+            _ => project_dir.to_path_buf(),
+        };
+        match &source.subpath {
+            Some(subpath) => base.join(subpath),
+            None => base,
         }
     }
 
     pub fn get_fetcher(source: &Source) -> Option<Box<dyn SourceFetcher>> {
         match source.source_type {
             crate::parse::SourceType::Git => Some(Box::new(GitFetcher)),
+            crate::parse::SourceType::Local { .. } => Some(Box::new(LocalFetcher)),
             _ => None,
         }
     }
+
+    /// Fetches `origin` and fast-forwards the checked-out branch of an existing
+    /// git clone. Non-git sources are considered already up to date. Errors if
+    /// the branch has diverged and cannot be fast-forwarded.
+    pub fn fast_forward(source: &Source, project_dir: &Path) -> Result<()> {
+        if !matches!(source.source_type, crate::parse::SourceType::Git) {
+            return Ok(());
+        }
+        // A pinned revision means a detached HEAD; leave it untouched so the
+        // clone stays reproducible instead of force-checking-out "HEAD".
+        if source.revision.is_some() {
+            return Ok(());
+        }
+
+        let repo = Repository::open(project_dir)?;
+        let mut remote = repo.find_remote("origin")?;
+        remote.fetch::<&str>(&[], Some(&mut fetch_options()), None)?;
+
+        let fetch_head = repo.find_reference("FETCH_HEAD")?;
+        let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
+        let (analysis, _) = repo.merge_analysis(&[&fetch_commit])?;
+
+        if analysis.is_up_to_date() {
+            return Ok(());
+        }
+        if !analysis.is_fast_forward() {
+            return Err("branch has diverged; cannot fast-forward".into());
+        }
+
+        let head = repo.head()?;
+        let refname = head.name().ok_or("invalid HEAD reference")?.to_string();
+        let mut reference = repo.find_reference(&refname)?;
+        reference.set_target(fetch_commit.id(), "pm: fast-forward")?;
+        repo.set_head(&refname)?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+        Ok(())
+    }
 }
 
 pub mod prompts {
@@ -76,6 +219,13 @@ pub mod prompts {
     }
 }
 
+/// A subset of a GitHub repository object as returned by the REST API.
+#[derive(Deserialize)]
+struct GitHubRepo {
+    name: String,
+    clone_url: String,
+}
+
 pub struct Config {
     config: ProjectConfig,
     prompter: Box<dyn prompts::Prompter>,
@@ -150,18 +300,7 @@ impl Config {
             .config
             .find_project(project_name)
             .ok_or("Project not found")?;
-        let project_path = Path::new(&project.path);
-        let project_dir = self.get_project_directory(project_path)?;
-
-        if !project_dir.exists() {
-            println!("Project is not on the filesystem");
-            if let Some(source) = &project.source {
-                println!("Fetching project from source...");
-                self.fetch_project_source(source, &project_dir)?;
-            } else {
-                return Err("Project source is not available".into());
-            }
-        }
+        let project_dir = self.resolve_usable_dir(project)?;
 
         Command::new(&self.config.editor)
             .arg(project_dir)
@@ -176,19 +315,128 @@ impl Config {
             .config
             .find_project(project_name)
             .ok_or("Project not found")?;
-        let project_path = Path::new(&project.path);
-        let project_dir = self.get_project_directory(project_path)?;
+        let project_dir = self.resolve_usable_dir(project)?;
+        println!("{}", project_dir.display());
+        Ok(())
+    }
 
+    /// Resolves the on-disk directory to use for a project, fetching it from
+    /// its source if it is not present yet. Honors `subpath` and `Local`
+    /// sources so callers act on the directory the source actually points at.
+    /// Status messages go to stderr to keep `pm pwd`'s stdout clean.
+    fn resolve_usable_dir(&self, project: &Project) -> Result<PathBuf> {
+        let project_dir = self.get_project_directory(Path::new(&project.path))?;
         if !project_dir.exists() {
-            println!("Project is not on the filesystem");
-            if let Some(source) = &project.source {
-                println!("Fetching project from source...");
-                self.fetch_project_source(source, &project_dir)?;
+            eprintln!("Project is not on the filesystem");
+            match &project.source {
+                Some(source) => {
+                    eprintln!("Fetching project from source...");
+                    self.fetch_project_source(source, &project_dir)
+                }
+                None => Err("Project source is not available".into()),
+            }
+        } else {
+            match &project.source {
+                Some(source) => Ok(fetchers::usable_dir(source, &project_dir)),
+                None => Ok(project_dir),
+            }
+        }
+    }
+
+    /// Builds the project using its build specification.
+    pub fn build_project(&self, project_name: &str) -> Result<ExitStatus> {
+        self.execute_build(project_name, false)
+    }
+
+    /// Runs the command from the project's build specification. It is the same
+    /// command as `build_project`, except that a `cargo` spec without a
+    /// `target` runs `cargo run`; other build types are not run afterwards.
+    pub fn run_project(&self, project_name: &str) -> Result<ExitStatus> {
+        self.execute_build(project_name, true)
+    }
+
+    /// Resolves the project directory (fetching it if missing), `cd`s into it
+    /// and spawns the command selected from the build spec for this platform.
+    fn execute_build(&self, project_name: &str, run: bool) -> Result<ExitStatus> {
+        let project = self
+            .config
+            .find_project(project_name)
+            .ok_or("Project not found")?;
+        let build = project
+            .build
+            .as_ref()
+            .ok_or("Project has no build specification")?;
+        let project_dir = self.resolve_usable_dir(project)?;
+
+        let mut command = resolve_build_command(build, run);
+        command.current_dir(&project_dir);
+        Ok(command.spawn()?.wait()?)
+    }
+
+    /// Ensures every project with a source is cloned and up to date on disk,
+    /// reporting per-project outcome without aborting on the first failure.
+    pub fn sync_projects(&self) -> Result<()> {
+        for project in &self.config.projects {
+            let Some(source) = &project.source else {
+                continue;
+            };
+            let project_dir = self.get_project_directory(Path::new(&project.path))?;
+            let result = if project_dir.exists() {
+                fetchers::fast_forward(source, &project_dir)
             } else {
-                return Err("Project source is not available".into());
+                self.fetch_project_source(source, &project_dir).map(|_| ())
+            };
+            match result {
+                Ok(()) => println!("  ok   {}", project.name),
+                Err(err) => eprintln!("  fail {}: {}", project.name, err),
             }
         }
-        println!("{}", project_dir.display());
+        Ok(())
+    }
+
+    /// Registers every public repository of a GitHub user or org as a project,
+    /// skipping any name that is already registered.
+    pub fn import_owner(&mut self, owner: &str) -> Result<()> {
+        let mut page = 1;
+        let mut imported = 0;
+        loop {
+            let url = format!(
+                "https://api.github.com/users/{}/repos?per_page=100&page={}",
+                owner, page
+            );
+            let body = ureq::get(&url)
+                .set("User-Agent", "pm")
+                .set("Accept", "application/vnd.github+json")
+                .call()?
+                .into_string()?;
+            let repos: Vec<GitHubRepo> = serde_json::from_str(&body)?;
+            if repos.is_empty() {
+                break;
+            }
+            for repo in repos {
+                if self.config.find_project(&repo.name).is_some() {
+                    println!("Skipping {} (already registered)", repo.name);
+                    continue;
+                }
+                self.config.add_project(Project {
+                    name: repo.name.clone(),
+                    path: repo.name,
+                    description: None,
+                    languages: Vec::new(),
+                    tags: Vec::new(),
+                    build: None,
+                    source: Some(Source {
+                        source_type: crate::parse::SourceType::Git,
+                        url: repo.clone_url,
+                        revision: None,
+                        subpath: None,
+                    }),
+                });
+                imported += 1;
+            }
+            page += 1;
+        }
+        println!("Imported {} project(s) from {}", imported, owner);
         Ok(())
     }
 
@@ -231,6 +479,8 @@ impl Config {
                 .to_string(),
             description: Some(project_description),
             languages: Vec::new(),
+            tags: Vec::new(),
+            build: None,
             source,
         });
         Ok(())
@@ -258,12 +508,53 @@ impl Config {
             path: source_name.to_string(),
             description: Some(project_description),
             languages: Vec::new(),
+            tags: Vec::new(),
+            build: None,
             source: Some(source),
         });
         Ok(())
     }
 
-    pub fn list_projects(&self, path: bool, description: bool, languages: bool, source: bool) {
+    /// Adds a tag to a project.
+    pub fn add_tag(&mut self, project_name: &str, tag: &str) -> Result<()> {
+        let project = self
+            .config
+            .find_project_mut(project_name)
+            .ok_or("Project not found")?;
+        if project.tags.iter().any(|t| t == tag) {
+            println!("Project already has tag '{}'", tag);
+        } else {
+            project.tags.push(tag.to_string());
+            println!("Added tag '{}' to {}", tag, project_name);
+        }
+        Ok(())
+    }
+
+    /// Removes a tag from a project.
+    pub fn remove_tag(&mut self, project_name: &str, tag: &str) -> Result<()> {
+        let project = self
+            .config
+            .find_project_mut(project_name)
+            .ok_or("Project not found")?;
+        let before = project.tags.len();
+        project.tags.retain(|t| t != tag);
+        if project.tags.len() == before {
+            println!("Project does not have tag '{}'", tag);
+        } else {
+            println!("Removed tag '{}' from {}", tag, project_name);
+        }
+        Ok(())
+    }
+
+    pub fn list_projects(
+        &self,
+        path: bool,
+        description: bool,
+        languages: bool,
+        source: bool,
+        tags: bool,
+        tag: Option<&str>,
+    ) {
         let mut table = Table::new();
         let mut headers = vec![];
 
@@ -279,12 +570,23 @@ impl Config {
         if source {
             headers.push(cell!("Source"));
         }
+        if tags {
+            headers.push(cell!("Tags"));
+        }
         if !headers.is_empty() {
             headers.insert(0, cell!("Name"));
             table.add_row(Row::new(headers));
         }
 
-        let mut sorted_projects = self.config.projects.clone();
+        let mut sorted_projects: Vec<Project> = match tag {
+            Some(tag) => self
+                .config
+                .projects_with_tag(tag)
+                .into_iter()
+                .cloned()
+                .collect(),
+            None => self.config.projects.clone(),
+        };
         sorted_projects.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
 
         for project in sorted_projects {
@@ -301,6 +603,9 @@ impl Config {
             if source {
                 row.push(cell!(project.source.as_ref().map_or("", |s| &s.url)));
             }
+            if tags {
+                row.push(cell!(project.tags.join(", ")));
+            }
             table.add_row(Row::new(row));
         }
 
@@ -361,6 +666,90 @@ impl Config {
     }
 }
 
+/// Wraps a command line so it runs through the system shell.
+fn shell_command(line: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(line);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(line);
+        command
+    }
+}
+
+/// Resolves the command to run for a build spec on the current platform.
+///
+/// A matching per-OS override always wins; otherwise the `type` decides the
+/// tooling. When `run` is set, `cargo` defaults to `run` instead of `build`.
+fn resolve_build_command(build: &Build, run: bool) -> Command {
+    let platform_override = if cfg!(target_os = "linux") {
+        build.linux.as_deref()
+    } else if cfg!(target_os = "macos") {
+        build.macos.as_deref()
+    } else if cfg!(target_os = "windows") {
+        build.windows.as_deref()
+    } else {
+        None
+    };
+    if let Some(line) = platform_override {
+        return shell_command(line);
+    }
+
+    match build.build_type {
+        BuildType::Make => {
+            let mut command = Command::new("make");
+            if let Some(target) = &build.target {
+                command.arg(target);
+            }
+            command
+        }
+        BuildType::Cargo => {
+            let mut command = Command::new("cargo");
+            let default = if run { "run" } else { "build" };
+            command.arg(build.target.as_deref().unwrap_or(default));
+            command
+        }
+        BuildType::Shell => shell_command(build.target.as_deref().unwrap_or_default()),
+        BuildType::Command => shell_command(build.command.as_deref().unwrap_or_default()),
+    }
+}
+
+/// Prints a shell function that wraps `pm` so `pm cd <project>` changes the
+/// caller's directory. A child process cannot `cd` its parent shell, so users
+/// `eval` this in their rc file; `pm cd` then runs `cd "$(pm pwd <project>)"`,
+/// which also fetches the project on demand via `pm pwd`.
+pub fn print_shell_integration(shell: ShellKind) {
+    match shell {
+        ShellKind::Bash | ShellKind::Zsh => println!(
+            r#"pm() {{
+    case "$1" in
+        cd)
+            shift
+            local dir
+            dir="$(command pm pwd "$@")" || return $?
+            cd "$dir"
+            ;;
+        *)
+            command pm "$@"
+            ;;
+    esac
+}}"#
+        ),
+        ShellKind::Fish => println!(
+            r#"function pm
+    if test "$argv[1]" = cd
+        set -l dir (command pm pwd $argv[2..-1]); or return $status
+        cd $dir
+    else
+        command pm $argv
+    end
+end"#
+        ),
+    }
+}
+
 /// Returns the path to the configuration file.
 pub fn get_config_file_path() -> Result<PathBuf> {
     if let Some(config_dir) = dirs::config_dir() {
@@ -389,6 +778,8 @@ fn get_git_project_source(project_dir: &Path) -> Option<Source> {
         Source {
             source_type: crate::parse::SourceType::Git,
             url,
+            revision: None,
+            subpath: None,
         }
     })
 }
@@ -20,6 +20,18 @@ pub fn handle_commands(cli: &Cli) -> Result<()> {
         Commands::Pwd { project_name } => {
             config.navigate_project(project_name)?;
         }
+        Commands::Build { project_name } => {
+            let status = config.build_project(project_name)?;
+            if !status.success() {
+                std::process::exit(status.code().unwrap_or(1));
+            }
+        }
+        Commands::Run { project_name } => {
+            let status = config.run_project(project_name)?;
+            if !status.success() {
+                std::process::exit(status.code().unwrap_or(1));
+            }
+        }
         Commands::Add { directory } => {
             config.add_project(directory)?;
             config.save_config()?;
@@ -33,6 +45,8 @@ pub fn handle_commands(cli: &Cli) -> Result<()> {
             config.add_project_from_source(Source {
                 source_type: parse::SourceType::Git,
                 url: url.to_string(),
+                revision: None,
+                subpath: None,
             })?;
             config.save_config()?;
         }
@@ -41,14 +55,36 @@ pub fn handle_commands(cli: &Cli) -> Result<()> {
             description,
             languages,
             source,
+            tags,
+            tag,
         } => {
-            config.list_projects(*path, *description, *languages, *source);
+            config.list_projects(*path, *description, *languages, *source, *tags, tag.as_deref());
         }
+        Commands::Tag { action } => match action {
+            cli::TagAction::Add { project_name, tag } => {
+                config.add_tag(project_name, tag)?;
+                config.save_config()?;
+            }
+            cli::TagAction::Rm { project_name, tag } => {
+                config.remove_tag(project_name, tag)?;
+                config.save_config()?;
+            }
+        },
         Commands::Edit => {
             let config_file = get_config_file_path()?;
             let editor = &config.inner().editor;
             Command::new(editor).arg(config_file).spawn()?.wait()?;
         }
+        Commands::Init { shell } => {
+            config::print_shell_integration(*shell);
+        }
+        Commands::Sync => {
+            config.sync_projects()?;
+        }
+        Commands::Import { owner } => {
+            config.import_owner(owner)?;
+            config.save_config()?;
+        }
         Commands::Inspect => {
             config.inspect();
         }
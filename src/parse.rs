@@ -33,6 +33,18 @@ impl ProjectConfig {
         self.projects.iter().find(|p| p.name == project_name)
     }
 
+    pub fn find_project_mut(&mut self, project_name: &str) -> Option<&mut Project> {
+        self.projects.iter_mut().find(|p| p.name == project_name)
+    }
+
+    /// Returns every project carrying the given tag.
+    pub fn projects_with_tag(&self, tag: &str) -> Vec<&Project> {
+        self.projects
+            .iter()
+            .filter(|p| p.tags.iter().any(|t| t == tag))
+            .collect()
+    }
+
     pub fn get_unregistered_folders(&self) -> Result<Vec<String>, std::io::Error> {
         let home_dir = match dirs::home_dir() {
             Some(path) => path,
@@ -96,14 +108,55 @@ pub struct Project {
     pub path: String,
     pub description: Option<String>,
     pub languages: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub build: Option<Build>,
     pub source: Option<Source>,
 }
 
+/// How a project is built or run.
+///
+/// The `type` selects the tooling; `target` is passed through to it (a make
+/// rule, a cargo subcommand, ...). For anything the built-in types
+/// don't cover, use `command` with a free-form shell line. The optional
+/// per-OS fields override the resolved command on that platform.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Build {
+    #[serde(rename = "type")]
+    pub build_type: BuildType,
+    pub target: Option<String>,
+    /// A free-form command, used when `type` is `command`.
+    pub command: Option<String>,
+    pub linux: Option<String>,
+    pub macos: Option<String>,
+    pub windows: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum BuildType {
+    /// Build with `make` (optionally a target rule)
+    #[serde(rename = "make")]
+    Make,
+    /// Build with `cargo` (optionally a subcommand)
+    #[serde(rename = "cargo")]
+    Cargo,
+    /// Run `target` through the system shell
+    #[serde(rename = "shell")]
+    Shell,
+    /// Run the free-form `command` through the system shell
+    #[serde(rename = "command")]
+    Command,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Source {
     #[serde(rename = "type")]
     pub source_type: SourceType,
     pub url: String,
+    /// An exact commit, tag or branch to check out, for reproducible clones.
+    pub revision: Option<String>,
+    /// A subdirectory inside the source to use as the project directory.
+    pub subpath: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -114,4 +167,7 @@ pub enum SourceType {
     /// A web URL
     #[serde(rename = "web")]
     Web,
+    /// A path on the local filesystem
+    #[serde(rename = "local")]
+    Local { path: String },
 }